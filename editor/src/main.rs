@@ -1,5 +1,5 @@
 use iced::{Subscription, Application, Command, Element, Font, Length, Settings, Theme};
-use iced::widget::{tooltip, button, text_editor, container, column, text, row, horizontal_space};
+use iced::widget::{tooltip, button, text_editor, container, column, text, row, horizontal_space, pick_list, scrollable};
 use iced::executor;
 use iced::theme;
 use iced::highlighter::{self, Highlighter};
@@ -20,24 +20,216 @@ fn main() -> iced::Result {
 }
 
 
-struct Editor {
+const AUTO_CLOSE_PAIRS: &[(char, char)] =
+    &[('(', ')'), ('{', '}'), ('[', ']'), ('"', '"'), ('\'', '\'')];
+
+const EDITOR_TEXT_SIZE: f32 = 16.0;
+const GUTTER_LINE_HEIGHT: f32 = EDITOR_TEXT_SIZE * 1.3;
+const GUTTER_VIEWPORT_LINES: f32 = 30.0;
+
+fn gutter_scrollable_id() -> scrollable::Id {
+    scrollable::Id::new("gutter")
+}
+
+struct Buffer {
+    id: u64,
     path: Option<PathBuf>,
     content: text_editor::Content,
     error: Option<Error>,
     is_dirty: bool,
+    gutter_scroll: f32,
+}
+
+impl Buffer {
+    fn new(id: u64) -> Self {
+        Self {
+            id,
+            path: None,
+            content: text_editor::Content::new(),
+            error: None,
+            is_dirty: false,
+            gutter_scroll: 0.0,
+        }
+    }
+
+    fn title(&self) -> String {
+        self.path
+            .as_deref()
+            .and_then(Path::file_name)
+            .and_then(|name| name.to_str())
+            .map(str::to_string)
+            .unwrap_or_else(|| String::from("New File"))
+    }
+
+    fn scroll_gutter_to_cursor(&mut self) -> bool {
+        let line = self.content.cursor_position().0 as f32;
+        let mut offset = self.gutter_scroll;
+
+        if line < offset {
+            offset = line;
+        } else if line >= offset + GUTTER_VIEWPORT_LINES - 1.0 {
+            offset = line - GUTTER_VIEWPORT_LINES + 1.0;
+        }
+
+        offset = offset.max(0.0);
+
+        if offset == self.gutter_scroll {
+            return false;
+        }
+
+        self.gutter_scroll = offset;
+        true
+    }
+
+    fn auto_close(&mut self, action: &text_editor::Action) -> Option<bool> {
+        let text_editor::Action::Edit(text_editor::Edit::Insert(c)) = *action else {
+            return None;
+        };
+
+        if is_closing(c) && self.char_at_cursor() == Some(c) {
+            self.content
+                .edit(text_editor::Action::Move(text_editor::Motion::Right));
+            return Some(false);
+        }
+
+        let closing = matching_close(c)?;
+
+        if closing == c {
+            let left = self.char_before_cursor();
+            let is_safe = matches!(left, None | Some(' ') | Some('\t'))
+                || left.is_some_and(|left| matching_close(left).is_some());
+
+            if !is_safe {
+                return None;
+            }
+        }
+
+        self.content
+            .edit(text_editor::Action::Edit(text_editor::Edit::Insert(c)));
+        self.content
+            .edit(text_editor::Action::Edit(text_editor::Edit::Insert(closing)));
+        self.content
+            .edit(text_editor::Action::Move(text_editor::Motion::Left));
+
+        Some(true)
+    }
+
+    fn char_before_cursor(&self) -> Option<char> {
+        let (line, column) = self.content.cursor_position();
+        column
+            .checked_sub(1)
+            .and_then(|index| self.line(line)?.chars().nth(index))
+    }
+
+    fn char_at_cursor(&self) -> Option<char> {
+        let (line, column) = self.content.cursor_position();
+        self.line(line)?.chars().nth(column)
+    }
+
+    fn line(&self, index: usize) -> Option<String> {
+        self.content.text().lines().nth(index).map(str::to_string)
+    }
+}
+
+struct Editor {
+    buffers: Vec<Buffer>,
+    active: usize,
+    next_buffer_id: u64,
+    theme: highlighter::Theme,
+    auto_close_pairs: bool,
+}
+
+impl Editor {
+    fn active(&self) -> &Buffer {
+        &self.buffers[self.active]
+    }
+
+    fn active_mut(&mut self) -> &mut Buffer {
+        &mut self.buffers[self.active]
+    }
+
+    fn next_buffer_id(&mut self) -> u64 {
+        let id = self.next_buffer_id;
+        self.next_buffer_id += 1;
+        id
+    }
+
+    fn close_tab_by_id(&mut self, id: u64) {
+        if let Some(index) = self.buffers.iter().position(|buffer| buffer.id == id) {
+            self.close_tab(index);
+        }
+    }
+
+    fn buffer_mut_by_id(&mut self, id: u64) -> Option<&mut Buffer> {
+        self.buffers.iter_mut().find(|buffer| buffer.id == id)
+    }
+
+    fn scroll_gutter_to(&mut self, offset: f32) -> Command<Message> {
+        self.active_mut().gutter_scroll = offset;
+
+        scrollable::scroll_to(
+            gutter_scrollable_id(),
+            scrollable::AbsoluteOffset {
+                x: 0.0,
+                y: offset * GUTTER_LINE_HEIGHT,
+            },
+        )
+    }
+
+    fn sync_gutter_to_cursor(&mut self) -> Command<Message> {
+        if self.active_mut().scroll_gutter_to_cursor() {
+            self.scroll_gutter_to(self.active().gutter_scroll)
+        } else {
+            Command::none()
+        }
+    }
+
+    fn close_tab(&mut self, index: usize) {
+        if index >= self.buffers.len() {
+            return;
+        }
+
+        self.buffers.remove(index);
+
+        if self.buffers.is_empty() {
+            let id = self.next_buffer_id();
+            self.buffers.push(Buffer::new(id));
+        }
+
+        if self.active >= self.buffers.len() {
+            self.active = self.buffers.len() - 1;
+        } else if index < self.active {
+            self.active -= 1;
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 enum Message {
-    Edit(text_editor::Action),  
+    Edit(text_editor::Action),
     Open,
     New,
     FileOpened(Result<(PathBuf, Arc<String>), Error>),
     FileSaved(Result<PathBuf    , Error>),
     Save,
+    SaveAs,
+    Print,
+    FilePrinted(Result<PathBuf, Error>),
+    ThemeChanged(highlighter::Theme),
+    TabSelected(usize),
+    TabClosed(usize),
+    CloseConfirmed(u64, DiscardChoice),
+    BufferSaved(u64, Result<PathBuf, Error>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DiscardChoice {
+    Save,
+    Discard,
+    Cancel,
 }
 
-impl Application for Editor { 
+impl Application for Editor {
     type Message = Message;
     type Theme = Theme;
     type Executor = iced::executor::Default;
@@ -47,10 +239,11 @@ impl Application for Editor {
     fn new(_flags: Self::Flags) -> (Self, Command<Message>) {
         (
             Self {
-            path: None, 
-            content: text_editor::Content::new(),
-            error: None,
-            is_dirty: true,
+            buffers: vec![Buffer::new(0)],
+            active: 0,
+            next_buffer_id: 1,
+            theme: highlighter::Theme::SolarizedDark,
+            auto_close_pairs: true,
         }, Command::perform(
             load_file(default_file()),
          Message::FileOpened,
@@ -65,51 +258,147 @@ impl Application for Editor {
     fn update(&mut self, message: Message) -> Command<Message> {
         match message {
             Message::Edit(action) => {
-                self.is_dirty = self.is_dirty || action.is_edit();
-                self.error = None;
-                self.content.edit(action);
+                self.active_mut().error = None;
 
-                Command::none()
-                }   
+                if let text_editor::Action::Scroll { lines } = action {
+                    self.active_mut().content.edit(action);
+                    let offset = self.active().gutter_scroll + lines as f32;
+
+                    return self.scroll_gutter_to(offset.max(0.0));
+                }
+
+                if self.auto_close_pairs {
+                    if let Some(mutated) = self.active_mut().auto_close(&action) {
+                        if mutated {
+                            self.active_mut().is_dirty = true;
+                        }
+                        return self.sync_gutter_to_cursor();
+                    }
+                }
+
+                let buffer = self.active_mut();
+                buffer.is_dirty = buffer.is_dirty || action.is_edit();
+                buffer.content.edit(action);
+
+                self.sync_gutter_to_cursor()
+                }
                 Message::New => {
-                    self.path = None;
-                    self.content = text_editor::Content::new();
-                    self.is_dirty = true;
+                    let id = self.next_buffer_id();
+                    self.buffers.push(Buffer::new(id));
+                    self.active = self.buffers.len() - 1;
                     Command::none()
                 }
             Message::Open => Command::perform(pick_file(), Message::FileOpened),
             Message::FileOpened(Ok((path, content))) => {
-                self.path = Some(path);
-                self.content = text_editor::Content::with(&content);
+                let id = self.next_buffer_id();
+                self.buffers.push(Buffer {
+                    id,
+                    path: Some(path),
+                    content: text_editor::Content::with(&content),
+                    error: None,
+                    is_dirty: false,
+                    gutter_scroll: 0.0,
+                });
+                self.active = self.buffers.len() - 1;
 
                 Command::none()
             }
             Message::Save => {
-                let text = self.content.text();
+                let text = self.active().content.text();
+                let path = self.active().path.clone();
+
+                Command::perform(save_file(path, text), Message::FileSaved)
 
-                Command::perform(save_file(self.path.clone(), text), Message::FileSaved)
-            
             }
-            Message::FileOpened(Ok((path, content))) => {
-                    self.path = Some(path); 
-                    self.is_dirty = false;
-                    Command::none()
-                }
+            Message::SaveAs => {
+                let text = self.active().content.text();
+
+                Command::perform(save_file(None, text), Message::FileSaved)
+            }
+            Message::Print => {
+                let text = self.active().content.text();
+
+                Command::perform(print_file(text), Message::FilePrinted)
+            }
+            Message::FilePrinted(Ok(_)) => Command::none(),
+            Message::FilePrinted(Err(error)) => {
+                self.active_mut().error = Some(error);
+                Command::none()
+            }
             Message::FileSaved(Ok(path)) => {
-                self.path = Some(path);
-                self.is_dirty = false;
+                self.active_mut().path = Some(path);
+                self.active_mut().is_dirty = false;
                 Command::none()
             }
             Message::FileSaved(Err(error)) => {
-                self.error = Some(error);
+                self.active_mut().error = Some(error);
+                Command::none()
+            }
+            Message::ThemeChanged(theme) => {
+                self.theme = theme;
                 Command::none()
             }
             Message::FileOpened(Err(error)) => {
-                self.error = Some(error);
+                self.active_mut().error = Some(error);
 
                 Command::none()
                 }
-        }   
+            Message::TabSelected(index) => {
+                if index < self.buffers.len() {
+                    self.active = index;
+                }
+                Command::none()
+            }
+            Message::TabClosed(index) => {
+                let Some(buffer) = self.buffers.get(index) else {
+                    return Command::none();
+                };
+
+                if buffer.is_dirty {
+                    let id = buffer.id;
+                    return Command::perform(confirm_discard_changes(), move |choice| {
+                        Message::CloseConfirmed(id, choice)
+                    });
+                }
+
+                self.close_tab(index);
+                Command::none()
+            }
+            Message::CloseConfirmed(id, choice) => match choice {
+                DiscardChoice::Cancel => Command::none(),
+                DiscardChoice::Discard => {
+                    self.close_tab_by_id(id);
+                    Command::none()
+                }
+                DiscardChoice::Save => {
+                    let Some(buffer) = self.buffer_mut_by_id(id) else {
+                        return Command::none();
+                    };
+
+                    let text = buffer.content.text();
+                    let path = buffer.path.clone();
+
+                    Command::perform(save_file(path, text), move |result| {
+                        Message::BufferSaved(id, result)
+                    })
+                }
+            },
+            Message::BufferSaved(id, Ok(path)) => {
+                if let Some(buffer) = self.buffer_mut_by_id(id) {
+                    buffer.path = Some(path);
+                    buffer.is_dirty = false;
+                }
+
+                self.close_tab_by_id(id);
+                Command::none()
+            }
+            Message::BufferSaved(id, Err(error)) => {
+                if let Some(buffer) = self.buffer_mut_by_id(id) {
+                    buffer.error = Some(error);
+                }
+                Command::none()
+            }
+        }
 
     }
 
@@ -123,11 +412,13 @@ impl Application for Editor {
     }
 
     fn view(&self) -> Element<'_, Self::Message> {
-        let input = text_editor(&self.content)
+        let editor = text_editor(&self.active().content)
         .on_edit(Message::Edit)
+        .size(EDITOR_TEXT_SIZE)
         .highlight::<Highlighter>(highlighter::Settings {
-            theme: highlighter::Theme::SolarizedDark,
+            theme: self.theme,
             extension: self
+            .active()
             .path
             .as_ref()
             .and_then(|path| path.extension()?.to_str())
@@ -137,43 +428,122 @@ impl Application for Editor {
          |highlight, _theme |
             highlight.to_format(),
     );
+
+        let current_line = self.active().content.cursor_position().0;
+        let line_count = self.active().content.line_count();
+        let digits = format!("{line_count}").len();
+
+        let gutter = column(
+            (1..=line_count)
+                .map(|line_number| {
+                    let number = text(format!("{line_number:>digits$}")).size(EDITOR_TEXT_SIZE);
+
+                    if line_number == current_line + 1 {
+                        number.style(theme::Text::Color(iced::Color::from_rgb8(0x8a, 0xb4, 0xf8))).into()
+                    } else {
+                        number.into()
+                    }
+                })
+                .collect::<Vec<Element<'_, Message>>>(),
+        )
+        .width(Length::Shrink);
+
+        let gutter = scrollable(gutter)
+        .id(gutter_scrollable_id())
+        .direction(scrollable::Direction::Vertical(
+            scrollable::Properties::new().width(0).scroller_width(0),
+        ));
+
+        let input = row![container(gutter).padding([0, 10, 0, 0]), editor];
+
+        let tabs = row(
+            self.buffers
+                .iter()
+                .enumerate()
+                .map(|(index, buffer)| tab(index, buffer, index == self.active))
+                .collect::<Vec<_>>(),
+        )
+        .spacing(4);
+
         let controls = row![
-            action(save_icon(), "Save File", self.is_dirty.then_some(Message::Save)),
+            action(save_icon(), "Save File", self.active().is_dirty.then_some(Message::Save)),
+            action(save_as_icon(), "Save As...", Some(Message::SaveAs)),
             action(open_icon(),"Open File", Some(Message::Open)),
             action(new_icon(), "New File", Some(Message::New)),
+            action(print_icon(), "Print", Some(Message::Print)),
+            horizontal_space(Length::Fill),
+            pick_list(highlighter::Theme::ALL, Some(self.theme), Message::ThemeChanged),
         ]
         .spacing(10);
         let status_bar = {
-                let status = if let Some(Error::IOFailed(error)) = self.error.as_ref() {
+                let status = if let Some(Error::IOFailed(error)) = self.active().error.as_ref() {
                 text(error.to_string())
         } else {
-            match self.path.as_deref().and_then(Path::to_str) {
+            match self.active().path.as_deref().and_then(Path::to_str) {
                 Some(path) => text(path).size(20),
                 None => text("New File  "),
             }
         };
-    
+
             let position = {
-                let (line, column) = self.content.cursor_position();
-    
+                let (line, column) = self.active().content.cursor_position();
+
                 text(format!("{}:{}", line + 1, column + 1))
 
              };
 
          row![status, horizontal_space(Length::Fill), position]
 
-        };   
+        };
 
-        container(column![controls, input, status_bar].spacing(10))
+        container(column![tabs, controls, input, status_bar].spacing(10))
         .padding(10)
-        .into() 
+        .into()
     }
 
     fn theme (&self) -> iced::Theme {
-        iced::Theme::Dark
+        if self.theme.is_dark() {
+            iced::Theme::Dark
+        } else {
+            iced::Theme::Light
+        }
     }
 }
 
+fn tab<'a>(index: usize, buffer: &Buffer, is_active: bool) -> Element<'a, Message> {
+    let label = if buffer.is_dirty {
+        format!("{}*", buffer.title())
+    } else {
+        buffer.title()
+    };
+
+    row![
+        button(text(label))
+            .on_press(Message::TabSelected(index))
+            .style(if is_active {
+                theme::Button::Primary
+            } else {
+                theme::Button::Secondary
+            }),
+        button(text('x'))
+            .on_press(Message::TabClosed(index))
+            .style(theme::Button::Text),
+    ]
+    .spacing(4)
+    .into()
+}
+
+fn is_closing(c: char) -> bool {
+    AUTO_CLOSE_PAIRS.iter().any(|(_, close)| *close == c)
+}
+
+fn matching_close(c: char) -> Option<char> {
+    AUTO_CLOSE_PAIRS
+        .iter()
+        .find(|(open, _)| *open == c)
+        .map(|(_, close)| *close)
+}
+
 fn icon<'a, Message>(codepoint: char) -> Element<'static, Message> {
     const ICON_FONT: Font = Font::with_name("save");
 
@@ -199,11 +569,37 @@ fn save_icon<'a>() -> Element<'a, Message> {
 fn open_icon<'a>() -> Element<'a, Message> {
     icon('\u{E801}')
 }
+fn save_as_icon<'a>() -> Element<'a, Message> {
+    icon('\u{F115}')
+}
+fn print_icon<'a>() -> Element<'a, Message> {
+    icon('\u{E802}')
+}
 
 fn default_file() -> PathBuf {
     PathBuf::from(format!("{}/src/main.rs", env!("CARGO_MANIFEST_DIR")))
 }
 
+async fn confirm_discard_changes() -> DiscardChoice {
+    let result = rfd::AsyncMessageDialog::new()
+    .set_title("Unsaved Changes")
+    .set_description("This file has unsaved changes. Save them before continuing?")
+    .set_level(rfd::MessageLevel::Warning)
+    .set_buttons(rfd::MessageButtons::YesNoCancelCustom(
+        "Save".to_string(),
+        "Discard".to_string(),
+        "Cancel".to_string(),
+    ))
+    .show()
+    .await;
+
+    match result {
+        rfd::MessageDialogResult::Custom(choice) if choice == "Save" => DiscardChoice::Save,
+        rfd::MessageDialogResult::Custom(choice) if choice == "Discard" => DiscardChoice::Discard,
+        _ => DiscardChoice::Cancel,
+    }
+}
+
 async fn pick_file() -> Result<(PathBuf, Arc<String>), Error> {
     let handle = rfd::AsyncFileDialog::new()
     .set_title("Open File")
@@ -214,11 +610,11 @@ async fn pick_file() -> Result<(PathBuf, Arc<String>), Error> {
     load_file(handle.path().to_owned()).await
 
 
-} 
+}
 async fn save_file(path: Option<PathBuf>, text: String) -> Result<PathBuf, Error> {
     let path = if let Some(path) = path  {
         path
- } else { 
+ } else {
         rfd::AsyncFileDialog::new()
         .set_title("Save File")
         .save_file()
@@ -244,8 +640,106 @@ async fn load_file(path: PathBuf) -> Result<(PathBuf, Arc<String>), Error> {
     Ok((path, contents))
 }
 
+const PRINT_PAGE_WIDTH_MM: f32 = 210.0;
+const PRINT_PAGE_HEIGHT_MM: f32 = 297.0;
+const PRINT_MARGIN_MM: f32 = 20.0;
+const PRINT_FONT_SIZE: f32 = 11.0;
+const PRINT_LINE_HEIGHT_MM: f32 = 5.0;
+const PRINT_LINES_PER_PAGE: usize = 50;
+
+const COURIER_ADVANCE_EM: f32 = 0.6;
+const MM_PER_POINT: f32 = 25.4 / 72.0;
+
+fn print_chars_per_line() -> usize {
+    let usable_width_mm = PRINT_PAGE_WIDTH_MM - 2.0 * PRINT_MARGIN_MM;
+    let char_width_mm = COURIER_ADVANCE_EM * PRINT_FONT_SIZE * MM_PER_POINT;
+
+    (usable_width_mm / char_width_mm) as usize
+}
+
+async fn print_file(text: String) -> Result<PathBuf, Error> {
+    let path = std::env::temp_dir().join("groovy-code-print.pdf");
+
+    render_pdf(&text, &path).map_err(|_| Error::IOFailed(io::ErrorKind::Other))?;
+
+    if open::that(&path).is_err() {
+        let target = rfd::AsyncFileDialog::new()
+        .set_title("Save PDF")
+        .set_file_name("document.pdf")
+        .save_file()
+        .await
+        .ok_or(Error::DialogClosed)
+        .map(|handle| handle.path().to_owned())?;
+
+        tokio::fs::copy(&path, &target)
+        .await
+        .map_err(|error| Error::IOFailed(error.kind()))?;
+
+        return Ok(target);
+    }
+
+    Ok(path)
+}
+
+fn render_pdf(text: &str, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    use printpdf::{BuiltinFont, Mm, PdfDocument};
+
+    let pages = paginate(text);
+
+    let (doc, first_page, first_layer) = PdfDocument::new(
+        "Groovy Code",
+        Mm(PRINT_PAGE_WIDTH_MM),
+        Mm(PRINT_PAGE_HEIGHT_MM),
+        "Layer 1",
+    );
+    let font = doc.add_builtin_font(BuiltinFont::Courier)?;
+
+    for (index, page_lines) in pages.iter().enumerate() {
+        let (page, layer) = if index == 0 {
+            (first_page, first_layer)
+        } else {
+            doc.add_page(Mm(PRINT_PAGE_WIDTH_MM), Mm(PRINT_PAGE_HEIGHT_MM), "Layer 1")
+        };
+        let layer = doc.get_page(page).get_layer(layer);
+
+        let mut y = PRINT_PAGE_HEIGHT_MM - PRINT_MARGIN_MM;
+        for line in page_lines {
+            layer.use_text(line, PRINT_FONT_SIZE, Mm(PRINT_MARGIN_MM), Mm(y), &font);
+            y -= PRINT_LINE_HEIGHT_MM;
+        }
+    }
+
+    doc.save(&mut std::io::BufWriter::new(std::fs::File::create(path)?))?;
+
+    Ok(())
+}
+
+fn paginate(text: &str) -> Vec<Vec<String>> {
+    let wrapped: Vec<String> = text
+        .lines()
+        .flat_map(|line| wrap_line(line, print_chars_per_line()))
+        .collect();
+
+    wrapped
+    .chunks(PRINT_LINES_PER_PAGE)
+    .map(|chunk| chunk.to_vec())
+    .collect()
+}
+
+fn wrap_line(line: &str, width: usize) -> Vec<String> {
+    if line.is_empty() {
+        return vec![String::new()];
+    }
+
+    line.chars()
+    .collect::<Vec<_>>()
+    .chunks(width)
+    .map(|chunk| chunk.iter().collect())
+    .collect()
+}
+
 #[derive(Debug, Clone)]
 enum Error {
     DialogClosed,
     IOFailed(io::ErrorKind),
-}
\ No newline at end of file
+}